@@ -1,7 +1,10 @@
 #[macro_export]
 /// Macro to reduce boilerplate for types implementing FromStr.
+///
+/// An optional leading JSON schema type sets `ConfigValue::schema_type`, e.g.
+/// `impl_config_value_for_fromstr!("integer": u8, u16)`.
 macro_rules! impl_config_value_for_fromstr {
-    ($($t:ty),*) => {
+    ($schema_type:literal: $($t:ty),*) => {
         $(
             impl ConfigValue for $t {
                 fn parse(key: &str, s: &str) -> Result<Self, ConfigError> {
@@ -11,12 +14,19 @@ macro_rules! impl_config_value_for_fromstr {
                     .map_err(|e| ConfigError::InvalidValue {
                         name: key.to_string(),
                         message: format!("{}", e),
+                        position: None,
                     })
                 }
                 fn to_config_string(&self) -> String {
                     self.to_string()
                 }
+                fn schema_type() -> &'static str {
+                    $schema_type
+                }
             }
         )*
     };
+    ($($t:ty),*) => {
+        $crate::impl_config_value_for_fromstr!("string": $($t),*);
+    };
 }
@@ -3,9 +3,12 @@ use crate::prelude::Validator;
 use crate::{Password, impl_config_value_for_fromstr};
 use indexmap::IndexMap;
 use std::any::Any;
-use std::collections::{HashMap, HashSet, LinkedList};
+use std::collections::{BTreeMap, HashMap, HashSet, LinkedList};
+use std::num::{NonZeroI64, NonZeroUsize};
 
 mod macros;
+#[cfg(feature = "json-schema")]
+mod openapi;
 
 /// The central bridge between raw string configurations and strongly-typed Rust values.
 ///
@@ -86,6 +89,19 @@ pub trait ConfigValue: Sized {
     /// user-provided values and developer-provided defaults, preventing invalid
     /// default configurations.
     fn to_config_string(&self) -> String;
+
+    /// The JSON schema type (`"string"`, `"integer"`, `"number"`, `"boolean"`, `"array"` or
+    /// `"object"`) describing this value when a `ConfigDef` is exported as a schema.
+    ///
+    /// Defaults to `"string"`, which matches any value's `to_config_string` form.
+    fn schema_type() -> &'static str {
+        "string"
+    }
+
+    /// An optional JSON schema `format` refining `schema_type`, e.g. `"password"`.
+    fn schema_format() -> Option<&'static str> {
+        None
+    }
 }
 
 /// A uniform, type-erased interface for configuration key metadata.
@@ -115,10 +131,20 @@ pub trait ConfigKeyTrait: Send + Sync {
     fn name(&self) -> &'static str;
     fn documentation(&self) -> Option<&String>;
     fn default_value_any(&self) -> Option<&dyn Any>;
+    /// Returns the default value rendered with `ConfigValue::to_config_string`.
+    fn default_value_str(&self) -> Option<String>;
+    /// The JSON schema type of the key's value, see [`ConfigValue::schema_type`].
+    fn schema_type(&self) -> &'static str;
+    /// The JSON schema format of the key's value, see [`ConfigValue::schema_format`].
+    fn schema_format(&self) -> Option<&'static str>;
     fn validator(&self) -> Option<&dyn Validator>;
     fn importance(&self) -> Option<Importance>;
     fn group(&self) -> Option<&String>;
     fn internal_config(&self) -> bool;
+    /// The version of the application in which this key was introduced, if declared.
+    fn since_version(&self) -> Option<&'static str>;
+    /// Whether a value must be supplied, i.e. the key has no default and isn't optional.
+    fn is_required(&self) -> bool;
     /// Clones the underlying concrete `ConfigKey<T>` and returns it as a new trait object.
     ///
     /// Trait objects (`dyn Trait`) are "unsized" and cannot implement `Clone` directly.
@@ -147,6 +173,8 @@ pub struct ConfigKey<T: 'static + Clone + Send + Sync + ConfigValue> {
     importance: Option<Importance>,
     group: Option<String>,
     internal_config: bool,
+    since_version: Option<&'static str>,
+    required: bool,
 }
 
 /// This struct acts as the central repository or "single source of truth" for all
@@ -183,6 +211,18 @@ pub trait FromConfigDef: Sized {
 
     /// Provides access to the static configuration schema (`ConfigDef`).
     fn config_def() -> Result<&'static ConfigDef, ConfigError>;
+
+    /// Converts an instance back into raw `(name, value)` property pairs.
+    ///
+    /// Values are produced with `ConfigValue::to_config_string`. For an instance returned by
+    /// `from_props`, feeding the pairs back into `from_props` yields an equal instance; this
+    /// doesn't hold for arbitrary instances (e.g. a `String` with surrounding whitespace, or an
+    /// `Option` field that is `None` but has a default). `Option` fields that are `None` are
+    /// skipped, and `#[merge]` fields contribute the pairs of the nested struct in declaration
+    /// order.
+    ///
+    /// Note that `Password` values are emitted in plaintext.
+    fn into_props_iter(instance: &Self) -> impl Iterator<Item = (&'static str, String)>;
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -202,6 +242,7 @@ impl<T: 'static + Clone + Send + Sync + ConfigValue> ConfigKey<T> {
         group: Option<String>,
         internal_config: bool,
     ) -> Self {
+        let required = default_value.is_none();
         Self {
             name,
             documentation,
@@ -210,8 +251,23 @@ impl<T: 'static + Clone + Send + Sync + ConfigValue> ConfigKey<T> {
             importance,
             group,
             internal_config,
+            since_version: None,
+            required,
         }
     }
+
+    /// Records the version in which this key was introduced.
+    pub fn with_since_version(mut self, since_version: Option<&'static str>) -> Self {
+        self.since_version = since_version;
+        self
+    }
+
+    /// Overrides whether the key is required. By default a key is required if it
+    /// has no default value.
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
 }
 
 impl Clone for Box<dyn ConfigKeyTrait> {
@@ -230,6 +286,15 @@ impl<T: 'static + Clone + Send + Sync + ConfigValue> ConfigKeyTrait for ConfigKe
     fn default_value_any(&self) -> Option<&dyn Any> {
         self.default_value.as_ref().map(|v| v as &dyn Any)
     }
+    fn default_value_str(&self) -> Option<String> {
+        self.default_value.as_ref().map(|v| v.to_config_string())
+    }
+    fn schema_type(&self) -> &'static str {
+        T::schema_type()
+    }
+    fn schema_format(&self) -> Option<&'static str> {
+        T::schema_format()
+    }
     fn validator(&self) -> Option<&dyn Validator> {
         self.validator.as_deref()
     }
@@ -242,6 +307,12 @@ impl<T: 'static + Clone + Send + Sync + ConfigValue> ConfigKeyTrait for ConfigKe
     fn internal_config(&self) -> bool {
         self.internal_config
     }
+    fn since_version(&self) -> Option<&'static str> {
+        self.since_version
+    }
+    fn is_required(&self) -> bool {
+        self.required
+    }
     fn clone_box(&self) -> Box<dyn ConfigKeyTrait> {
         Box::new(self.clone())
     }
@@ -258,6 +329,53 @@ impl ConfigDef {
     pub fn config_keys(&self) -> &IndexMap<&'static str, Box<dyn ConfigKeyTrait>> {
         &self.config_keys
     }
+
+    /// Iterates over the keys that must be supplied, see [`ConfigKeyTrait::is_required`].
+    pub fn iter_required(&self) -> impl Iterator<Item = &dyn ConfigKeyTrait> {
+        self.iter_keys().filter(|k| k.is_required())
+    }
+
+    /// Iterates over the keys that may be omitted.
+    pub fn iter_optional(&self) -> impl Iterator<Item = &dyn ConfigKeyTrait> {
+        self.iter_keys().filter(|k| !k.is_required())
+    }
+
+    /// Iterates over the keys marked as `internal_config`.
+    pub fn iter_internal(&self) -> impl Iterator<Item = &dyn ConfigKeyTrait> {
+        self.iter_keys().filter(|k| k.internal_config())
+    }
+
+    /// Iterates over the keys that declare a default value.
+    pub fn iter_with_defaults(&self) -> impl Iterator<Item = &dyn ConfigKeyTrait> {
+        self.iter_keys().filter(|k| k.default_value_any().is_some())
+    }
+
+    fn iter_keys(&self) -> impl Iterator<Item = &dyn ConfigKeyTrait> {
+        self.config_keys.values().map(|k| k.as_ref())
+    }
+
+    /// Inserts the default value of every key that is absent from `props` and has a default.
+    ///
+    /// Values already present in `props` are left untouched.
+    pub fn apply_defaults(&self, props: &mut HashMap<String, String>) {
+        for key in self.config_keys.values() {
+            if !props.contains_key(key.name())
+                && let Some(default) = key.default_value_str()
+            {
+                props.insert(key.name().to_string(), default);
+            }
+        }
+    }
+
+    /// Returns a copy of `props` with defaults filled in, see [`ConfigDef::apply_defaults`].
+    pub fn with_defaults_applied(
+        &self,
+        props: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        let mut props = props.clone();
+        self.apply_defaults(&mut props);
+        props
+    }
 }
 
 impl TryFrom<Vec<Box<dyn ConfigKeyTrait>>> for ConfigDef {
@@ -266,6 +384,7 @@ impl TryFrom<Vec<Box<dyn ConfigKeyTrait>>> for ConfigDef {
     fn try_from(keys: Vec<Box<dyn ConfigKeyTrait>>) -> Result<Self, Self::Error> {
         let mut config_keys = IndexMap::with_capacity(keys.len());
         for key in keys {
+            check_default_separators(key.as_ref())?;
             if let Some(existing_key) = config_keys.insert(key.name(), key) {
                 return Err(ConfigError::ValidationFailed {
                     name: existing_key.name().to_string(),
@@ -273,6 +392,7 @@ impl TryFrom<Vec<Box<dyn ConfigKeyTrait>>> for ConfigDef {
                         "Configuration key '{}' is defined twice.",
                         existing_key.name()
                     ),
+                    position: None,
                 });
             }
         }
@@ -292,10 +412,54 @@ impl TryFrom<Vec<Box<dyn ConfigKeyTrait>>> for ConfigDef {
     }
 }
 
+/// List and map defaults are validated and re-parsed via their joined string form, so a
+/// separator inside an item would silently change the value.
+fn check_default_separators(key: &dyn ConfigKeyTrait) -> Result<(), ConfigError> {
+    let default = key.default_value_any();
+    let message = if let Some(items) = default.and_then(|v| v.downcast_ref::<Vec<String>>()) {
+        items.iter().find(|item| item.contains(',')).map(|item| {
+            format!(
+                "Default value item '{}' for configuration '{}' must not contain ','.",
+                item,
+                key.name()
+            )
+        })
+    } else if let Some(map) = default.and_then(|v| v.downcast_ref::<BTreeMap<String, String>>()) {
+        map.iter()
+            .find(|(k, v)| k.contains([',', '=']) || v.contains(','))
+            .map(|(k, v)| {
+                format!(
+                    "Default value entry '{}={}' for configuration '{}' must not contain ',' \
+                    or '=' in its key, or ',' in its value.",
+                    k,
+                    v,
+                    key.name()
+                )
+            })
+    } else {
+        None
+    };
+
+    match message {
+        Some(message) => Err(ConfigError::ValidationFailed {
+            name: key.name().to_string(),
+            message,
+            position: None,
+        }),
+        None => Ok(()),
+    }
+}
+
+impl_config_value_for_fromstr!("boolean": bool);
+
 impl_config_value_for_fromstr!(
-    bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+    "integer": u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
 );
 
+impl_config_value_for_fromstr!("number": f32, f64);
+
+impl_config_value_for_fromstr!("integer": NonZeroUsize, NonZeroI64);
+
 impl ConfigValue for String {
     fn parse(_key: &str, s: &str) -> Result<Self, ConfigError> {
         Ok(s.trim().to_string())
@@ -316,6 +480,52 @@ impl ConfigValue for Vec<String> {
     fn to_config_string(&self) -> String {
         self.join(",")
     }
+    fn schema_type() -> &'static str {
+        "array"
+    }
+}
+
+/// Parses `key=value,key2=value2` into a `BTreeMap`, trimming keys and values.
+impl ConfigValue for BTreeMap<String, String> {
+    fn parse(key: &str, s: &str) -> Result<Self, ConfigError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+        let mut map = BTreeMap::new();
+        for entry in s.split(',') {
+            let (k, v) = match entry.split_once('=') {
+                Some((k, v)) if !k.trim().is_empty() => (k.trim(), v.trim()),
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        name: key.to_string(),
+                        message: format!(
+                            "Map entry '{}' is not in the form 'key=value'",
+                            entry.trim()
+                        ),
+                        position: None,
+                    });
+                }
+            };
+            if map.insert(k.to_string(), v.to_string()).is_some() {
+                return Err(ConfigError::InvalidValue {
+                    name: key.to_string(),
+                    message: format!("Map key '{}' must not be duplicated", k),
+                    position: None,
+                });
+            }
+        }
+        Ok(map)
+    }
+    fn to_config_string(&self) -> String {
+        self.iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+    fn schema_type() -> &'static str {
+        "object"
+    }
 }
 
 impl ConfigValue for Password {
@@ -325,4 +535,7 @@ impl ConfigValue for Password {
     fn to_config_string(&self) -> String {
         self.password().to_string()
     }
+    fn schema_format() -> Option<&'static str> {
+        Some("password")
+    }
 }
@@ -0,0 +1,182 @@
+use crate::{ConfigDef, ConfigKeyTrait, Importance};
+use serde_json::{Map, Value, json};
+
+impl ConfigDef {
+    /// Emits an OpenAPI 3.1 fragment describing this configuration as an object schema.
+    ///
+    /// The schema is registered as `components/schemas/<tag>`. Each key becomes a property
+    /// carrying its documentation, default and JSON type; importance, group and version
+    /// metadata are emitted as `x-` extensions. Required keys are listed in `required`.
+    pub fn to_openapi_schema(&self, tag: &str) -> Value {
+        let properties: Map<String, Value> = self
+            .config_keys()
+            .values()
+            .map(|key| (key.name().to_string(), property_schema(key.as_ref())))
+            .collect();
+        let required: Vec<&str> = self.iter_required().map(|k| k.name()).collect();
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), json!("object"));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), json!(required));
+        }
+        schema.insert("properties".to_string(), Value::Object(properties));
+
+        json!({ "components": { "schemas": { tag: schema } } })
+    }
+}
+
+fn property_schema(key: &dyn ConfigKeyTrait) -> Value {
+    let schema_type = key.schema_type();
+    let mut schema = match schema_type {
+        "array" => json!({ "type": "array", "items": { "type": "string" } }),
+        "object" => json!({ "type": "object", "additionalProperties": { "type": "string" } }),
+        schema_type => json!({ "type": schema_type }),
+    };
+    let schema_map = schema.as_object_mut().unwrap();
+
+    if let Some(documentation) = key.documentation() {
+        schema_map.insert("description".to_string(), json!(documentation));
+    }
+    if let Some(format) = key.schema_format() {
+        schema_map.insert("format".to_string(), json!(format));
+    }
+    // Secrets are write-only and their defaults are never exposed.
+    if key.schema_format() == Some("password") {
+        schema_map.insert("writeOnly".to_string(), json!(true));
+    } else if let Some(default) = key.default_value_str() {
+        schema_map.insert("default".to_string(), default_value(schema_type, &default));
+    }
+    if let Some(importance) = key.importance() {
+        let importance = match importance {
+            Importance::HIGH => "high",
+            Importance::MEDIUM => "medium",
+            Importance::LOW => "low",
+        };
+        schema_map.insert("x-importance".to_string(), json!(importance));
+    }
+    if let Some(group) = key.group() {
+        schema_map.insert("x-group".to_string(), json!(group));
+    }
+    if let Some(since_version) = key.since_version() {
+        schema_map.insert("x-since-version".to_string(), json!(since_version));
+    }
+    if key.internal_config() {
+        schema_map.insert("x-internal".to_string(), json!(true));
+    }
+    schema
+}
+
+/// Converts a default rendered by `to_config_string` into the matching JSON value.
+fn default_value(schema_type: &str, default: &str) -> Value {
+    match schema_type {
+        "integer" | "number" | "boolean" => {
+            serde_json::from_str(default).unwrap_or_else(|_| json!(default))
+        }
+        "array" if default.is_empty() => json!([]),
+        "array" => json!(default.split(',').collect::<Vec<_>>()),
+        "object" => Value::Object(
+            default
+                .split(',')
+                .filter_map(|entry| entry.split_once('='))
+                .map(|(k, v)| (k.to_string(), json!(v)))
+                .collect(),
+        ),
+        _ => json!(default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    /// A custom value declaring its own schema type.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Millis(u64);
+
+    impl ConfigValue for Millis {
+        fn parse(key: &str, s: &str) -> Result<Self, ConfigError> {
+            u64::parse(key, s).map(Millis)
+        }
+        fn to_config_string(&self) -> String {
+            self.0.to_string()
+        }
+        fn schema_type() -> &'static str {
+            "integer"
+        }
+    }
+
+    #[derive(EasyConfig)]
+    struct TestConfig {
+        #[attr(name = "server.port", default = 8080, importance = Importance::HIGH,
+        documentation = "The port to listen on.", group = "server", since_version = "1.2.0")]
+        _port: u16,
+        #[attr(default = 0.5)]
+        _ratio: f64,
+        #[attr(importance = Importance::LOW)]
+        _enabled: bool,
+        #[attr(default = vec!["a".to_string(), "b".to_string()])]
+        _tags: Vec<String>,
+        #[attr(default = BTreeMap::from([("k".to_string(), "v".to_string())]))]
+        _labels: BTreeMap<String, String>,
+        #[attr(default = Password::new("secret".to_string()), internal_config = true)]
+        _password: Password,
+        #[attr(documentation = "docs")]
+        _name: Option<String>,
+        #[attr(default = Millis(100))]
+        _timeout: Millis,
+    }
+
+    #[test]
+    fn test_to_openapi_schema() {
+        let schema = TestConfig::config_def()
+            .unwrap()
+            .to_openapi_schema("TestConfig");
+
+        assert_eq!(
+            schema,
+            json!({
+                "components": {
+                    "schemas": {
+                        "TestConfig": {
+                            "type": "object",
+                            "required": ["_enabled"],
+                            "properties": {
+                                "server.port": {
+                                    "type": "integer",
+                                    "description": "The port to listen on.",
+                                    "default": 8080,
+                                    "x-importance": "high",
+                                    "x-group": "server",
+                                    "x-since-version": "1.2.0"
+                                },
+                                "_ratio": { "type": "number", "default": 0.5 },
+                                "_enabled": { "type": "boolean", "x-importance": "low" },
+                                "_tags": {
+                                    "type": "array",
+                                    "items": { "type": "string" },
+                                    "default": ["a", "b"]
+                                },
+                                "_labels": {
+                                    "type": "object",
+                                    "additionalProperties": { "type": "string" },
+                                    "default": { "k": "v" }
+                                },
+                                "_password": {
+                                    "type": "string",
+                                    "format": "password",
+                                    "writeOnly": true,
+                                    "x-internal": true
+                                },
+                                "_name": { "type": "string", "description": "docs" },
+                                "_timeout": { "type": "integer", "default": 100 }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
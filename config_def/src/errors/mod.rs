@@ -1,9 +1,111 @@
+/// With the `serde` feature, errors serialize as `{"type": "<Variant>", "details": ...}`.
+/// Adjacent tagging is used because the `MissingName` newtype variant can't be
+/// represented with an internal tag.
 #[derive(thiserror::Error, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "details")
+)]
 pub enum ConfigError {
     #[error("Missing required configuration name: '{0}'")]
     MissingName(String),
-    #[error("Failed to parse name '{name}': {message}")]
-    InvalidValue { name: String, message: String },
-    #[error("Validation failed for name '{name}': {message}")]
-    ValidationFailed { name: String, message: String },
+    #[error("Failed to parse name '{name}': {message}{}", display_position(.position))]
+    InvalidValue {
+        name: String,
+        message: String,
+        /// The `(line, column)` of the value in its source file, if known.
+        position: Option<(usize, usize)>,
+    },
+    #[error("Validation failed for name '{name}': {message}{}", display_position(.position))]
+    ValidationFailed {
+        name: String,
+        message: String,
+        /// The `(line, column)` of the value in its source file, if known.
+        position: Option<(usize, usize)>,
+    },
+}
+
+impl ConfigError {
+    /// Attaches a source `(line, column)` to `InvalidValue` and `ValidationFailed` errors
+    /// that don't have one yet. Other errors are returned unchanged.
+    pub fn with_position(mut self, line: usize, column: usize) -> Self {
+        match &mut self {
+            ConfigError::InvalidValue { position, .. }
+            | ConfigError::ValidationFailed { position, .. } => {
+                position.get_or_insert((line, column));
+            }
+            ConfigError::MissingName(_) => {}
+        }
+        self
+    }
+}
+
+fn display_position(position: &Option<(usize, usize)>) -> String {
+    position
+        .map(|(line, _)| format!(" at line {line}"))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_position() {
+        let error = ConfigError::InvalidValue {
+            name: "a".to_string(),
+            message: "bad".to_string(),
+            position: None,
+        };
+        assert_eq!(error.to_string(), "Failed to parse name 'a': bad");
+
+        let error = error.with_position(3, 5).with_position(7, 1);
+        assert_eq!(error.to_string(), "Failed to parse name 'a': bad at line 3");
+
+        let error = ConfigError::MissingName("a".to_string()).with_position(3, 5);
+        assert_eq!(error, ConfigError::MissingName("a".to_string()));
+    }
+
+    #[cfg(feature = "serde")]
+    fn assert_round_trip(error: ConfigError, expected_json: &str) {
+        let json = serde_json::to_string(&error).unwrap();
+        assert_eq!(json, expected_json);
+        assert_eq!(serde_json::from_str::<ConfigError>(&json).unwrap(), error);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_missing_name_round_trip() {
+        assert_round_trip(
+            ConfigError::MissingName("a".to_string()),
+            r#"{"type":"MissingName","details":"a"}"#,
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_invalid_value_round_trip() {
+        assert_round_trip(
+            ConfigError::InvalidValue {
+                name: "a".to_string(),
+                message: "bad".to_string(),
+                position: Some((3, 5)),
+            },
+            r#"{"type":"InvalidValue","details":{"name":"a","message":"bad","position":[3,5]}}"#,
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_validation_failed_round_trip() {
+        assert_round_trip(
+            ConfigError::ValidationFailed {
+                name: "a".to_string(),
+                message: "bad".to_string(),
+                position: None,
+            },
+            r#"{"type":"ValidationFailed","details":{"name":"a","message":"bad","position":null}}"#,
+        );
+    }
 }
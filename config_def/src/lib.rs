@@ -1,3 +1,14 @@
+//! Define, parse and validate application configurations, inspired by Apache Kafka's `ConfigDef`.
+//!
+//! # Thread safety
+//!
+//! The `ConfigDef` of a struct deriving `EasyConfig` (or `ConfigSchema`) is built lazily on the
+//! first call to `config_def()` and stored in a `static` `once_cell::sync::OnceCell`. If several
+//! threads call it concurrently, only one of them runs the initialization while the others
+//! block, and all of them observe the same `&'static ConfigDef`. If initialization fails (e.g.
+//! a key is defined twice), every caller gets the error and the cell stays empty, so the next
+//! call retries instead of caching a half-built definition.
+
 pub mod prelude;
 
 pub use prelude::*;
@@ -5,16 +16,18 @@ pub use types::password::Password;
 
 mod core;
 mod errors;
+mod sources;
 mod types;
 mod validators;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use easy_config_macros::EasyConfig;
+    use easy_config_macros::{ConfigSchema, EasyConfig};
     use once_cell::sync::Lazy;
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
     use std::fmt::Debug;
+    use std::num::{NonZeroI64, NonZeroUsize};
 
     const H: &str = "h";
     const DOC: &str = "Docs for 'a'. ";
@@ -115,6 +128,22 @@ mod tests {
         assert_eq!(config.val, "value");
     }
 
+    #[test]
+    fn test_since_version() {
+        #[derive(EasyConfig)]
+        struct TestConfig {
+            #[attr(default = 1, since_version = "1.2.0")]
+            _a: i32,
+            #[attr(default = 2)]
+            _b: i32,
+        }
+
+        let def = TestConfig::config_def().unwrap();
+
+        assert_eq!(def.find_key("_a").unwrap().since_version(), Some("1.2.0"));
+        assert_eq!(def.find_key("_b").unwrap().since_version(), None);
+    }
+
     #[test]
     fn test_null_default() {
         #[derive(EasyConfig, Debug, PartialEq)]
@@ -129,6 +158,111 @@ mod tests {
         assert_eq!(config.a, None);
     }
 
+    #[test]
+    fn test_apply_defaults() {
+        #[derive(EasyConfig)]
+        struct TestConfig {
+            #[attr(default = 5, importance = Importance::HIGH, documentation = "docs")]
+            _a: i32,
+            #[attr(name = "prop.b", default = vec!["x".to_string(), "y".to_string()])]
+            _b: Vec<String>,
+            #[attr(default = "hello".to_string())]
+            _c: String,
+            #[attr(documentation = "docs")]
+            _d: Option<i32>,
+        }
+
+        let def = TestConfig::config_def().unwrap();
+        let mut props = HashMap::new();
+        props.insert("_c".to_string(), "world".to_string());
+
+        let filled = def.with_defaults_applied(&props);
+        assert_eq!(props.len(), 1);
+
+        def.apply_defaults(&mut props);
+        assert_eq!(props, filled);
+        assert_eq!(
+            props,
+            HashMap::from([
+                ("_a".to_string(), "5".to_string()),
+                ("prop.b".to_string(), "x,y".to_string()),
+                ("_c".to_string(), "world".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_iter_keys_by_kind() {
+        #[derive(EasyConfig)]
+        struct TestConfig {
+            #[attr(importance = Importance::HIGH, documentation = "docs")]
+            _a: i32,
+            #[attr(default = 1)]
+            _b: i32,
+            #[attr(documentation = "docs")]
+            _c: Option<String>,
+            #[attr(default = "x".to_string(), internal_config = true)]
+            _d: Option<String>,
+            #[attr(internal_config = true)]
+            _e: String,
+        }
+
+        let def = TestConfig::config_def().unwrap();
+        let names =
+            |keys: Vec<&dyn ConfigKeyTrait>| keys.iter().map(|k| k.name()).collect::<Vec<_>>();
+
+        assert_eq!(names(def.iter_required().collect()), vec!["_a", "_e"]);
+        assert_eq!(names(def.iter_optional().collect()), vec!["_b", "_c", "_d"]);
+        assert_eq!(names(def.iter_internal().collect()), vec!["_d", "_e"]);
+        assert_eq!(names(def.iter_with_defaults().collect()), vec!["_b", "_d"]);
+    }
+
+    #[test]
+    fn test_config_def_concurrent_initialization() {
+        use std::sync::{Arc, Barrier};
+        use std::thread;
+
+        #[derive(EasyConfig)]
+        struct TestConfig {
+            #[attr(default = 1)]
+            _a: i32,
+        }
+
+        #[derive(EasyConfig)]
+        struct DuplicateKeyConfig {
+            #[attr(name = "a", default = 1)]
+            _a1: i32,
+            #[attr(name = "a", default = 2)]
+            _a2: i32,
+        }
+
+        const THREADS: usize = 16;
+        let barrier = Arc::new(Barrier::new(THREADS));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    let def = TestConfig::config_def().unwrap() as *const ConfigDef as usize;
+                    let duplicate = DuplicateKeyConfig::config_def().map(|_| ());
+                    (def, duplicate)
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        let first = results[0].0;
+        for (def, duplicate) in results {
+            assert_eq!(def, first, "All threads must observe the same ConfigDef");
+            assert!(
+                matches!(&duplicate, Err(ConfigError::ValidationFailed { name, .. }) if name == "a"),
+                "Expected ValidationFailed error but got {:?}",
+                &duplicate
+            );
+        }
+    }
+
     #[test]
     fn test_missing_required() {
         #[derive(EasyConfig)]
@@ -193,6 +327,18 @@ mod tests {
         &["hello", "42.5", "922337203685477580700"]
     );
 
+    test_bad_inputs!(
+        test_bad_inputs_for_non_zero_usize,
+        NonZeroUsize,
+        &["0", "-1", "hello"]
+    );
+
+    test_bad_inputs!(
+        test_bad_inputs_for_non_zero_long,
+        NonZeroI64,
+        &["0", "-0", "42.5", "hello"]
+    );
+
     test_bad_inputs!(test_bad_inputs_for_double, f64, &["hello", "not-a-number"]);
 
     test_bad_inputs!(
@@ -201,6 +347,69 @@ mod tests {
         &["hello", "truee", "fals", "0", "1"]
     );
 
+    test_bad_inputs!(
+        test_bad_inputs_for_btree_map,
+        BTreeMap<String, String>,
+        &["a", "a=1,b", "=1", "a=1,,b=2", "a=1, a =2"]
+    );
+
+    #[test]
+    fn test_btree_map() {
+        #[derive(Debug, EasyConfig)]
+        struct TestConfig {
+            #[attr(importance = Importance::HIGH, documentation = "docs")]
+            a: BTreeMap<String, String>,
+            #[attr(default = BTreeMap::new(), importance = Importance::HIGH, documentation = "docs")]
+            b: BTreeMap<String, String>,
+        }
+
+        let mut props = HashMap::new();
+        props.insert("a".to_string(), " z = 1, a=2 ,m= ".to_string());
+        let config = TestConfig::from_props(&props).unwrap();
+
+        assert_eq!(
+            config.a,
+            BTreeMap::from([
+                ("a".to_string(), "2".to_string()),
+                ("m".to_string(), "".to_string()),
+                ("z".to_string(), "1".to_string()),
+            ])
+        );
+        assert!(config.b.is_empty());
+        assert_eq!(config.a.to_config_string(), "a=2,m=,z=1");
+    }
+
+    #[test]
+    fn test_btree_map_default_with_separators() {
+        #[derive(Debug, EasyConfig)]
+        struct CommaInValue {
+            #[attr(default = BTreeMap::from([("k".to_string(), "a,b".to_string())]))]
+            _a: BTreeMap<String, String>,
+        }
+
+        #[derive(Debug, EasyConfig)]
+        struct EqualsInKey {
+            #[attr(default = BTreeMap::from([("k=1".to_string(), "v".to_string())]))]
+            _a: BTreeMap<String, String>,
+        }
+
+        let config = CommaInValue::from_props(&HashMap::new());
+        assert!(
+            matches!(&config, Err(ConfigError::ValidationFailed { name, message, .. })
+                if name == "_a" && message.contains("'k=a,b'")),
+            "Expected ValidationFailed error, but got {:?}",
+            &config
+        );
+
+        let config = EqualsInKey::from_props(&HashMap::new());
+        assert!(
+            matches!(&config, Err(ConfigError::ValidationFailed { name, message, .. })
+                if name == "_a" && message.contains("'k=1=v'")),
+            "Expected ValidationFailed error, but got {:?}",
+            &config
+        );
+    }
+
     #[test]
     fn test_invalid_default_range() {
         #[derive(Debug, EasyConfig)]
@@ -213,7 +422,7 @@ mod tests {
         let config = TestConfig::from_props(&HashMap::new());
 
         assert!(
-            matches!(&config, Err(ConfigError::ValidationFailed{name, message})
+            matches!(&config, Err(ConfigError::ValidationFailed{name, message, ..})
             if name == "_a" && message.contains("Value -1 must be at least 0")
             ),
             "Expected ValidationFailed error, but got {:?}",
@@ -237,7 +446,7 @@ mod tests {
         assert!(
             matches!(
                 &config,
-                Err(ConfigError::ValidationFailed { name, message })
+                Err(ConfigError::ValidationFailed { name, message, .. })
                     if name == "_a" && message.contains("must be one of: valid, values")
             ),
             "Expected ValidationFailed error, but got {:?}",
@@ -317,6 +526,62 @@ mod tests {
         &["bad", "inputs", "DEFAULT"]
     );
 
+    test_validators!(
+        test_non_empty_string_validator,
+        String,
+        "default".to_string(),
+        ValidString::non_empty(),
+        &["a", " a ", "hello world"],
+        &["", " ", "\t\n"]
+    );
+
+    test_validators!(
+        test_dns_label_validator,
+        String,
+        "localhost".to_string(),
+        ValidString::dns_label(),
+        &["a", "my-host", "com3", " host1 ", &"a".repeat(63)],
+        &[
+            "",
+            "3com",
+            "123",
+            "-host",
+            "host-",
+            "my_host",
+            "my host",
+            "a.b",
+            &"a".repeat(64)
+        ]
+    );
+
+    test_validators!(
+        test_dns_name_validator,
+        String,
+        "localhost".to_string(),
+        ValidString::dns_name(),
+        &[
+            "localhost",
+            "broker-1.example.com",
+            "a.b.c",
+            "3com.example.com",
+            "1password.com",
+            "163.com",
+            "s3.1e100.net"
+        ],
+        &[
+            "",
+            "1.2.3.4",
+            "123",
+            "example.123",
+            "example..com",
+            ".example.com",
+            "example.com.",
+            "-a.example.com",
+            "ex ample.com",
+            &vec!["a".repeat(63); 4].join(".")
+        ]
+    );
+
     test_validators!(
         test_list_validator,
         Vec<String>,
@@ -326,6 +591,46 @@ mod tests {
         &["4", "5", "6"]
     );
 
+    #[test]
+    fn test_is_value_valid() {
+        let validator = Range::between(0, 10);
+        let candidates = ["-1", "0", "5", "11", "abc"];
+
+        let valid: Vec<_> = candidates
+            .into_iter()
+            .filter(|c| validator.is_value_valid(c))
+            .collect();
+
+        assert_eq!(valid, vec!["0", "5"]);
+    }
+
+    #[test]
+    fn test_list_default_round_trip() {
+        let default = vec!["a".to_string(), "b".to_string()];
+        let round_trip =
+            <Vec<String> as ConfigValue>::parse("name", &default.to_config_string()).unwrap();
+        assert_eq!(round_trip, default);
+
+        let empty: Vec<String> = Vec::new();
+        let round_trip =
+            <Vec<String> as ConfigValue>::parse("name", &empty.to_config_string()).unwrap();
+        assert_eq!(round_trip, empty);
+
+        #[derive(Debug, EasyConfig)]
+        struct TestConfig {
+            #[attr(default = vec!["a".to_string(), "b,c".to_string()])]
+            _a: Vec<String>,
+        }
+
+        let config = TestConfig::from_props(&HashMap::new());
+        assert!(
+            matches!(&config, Err(ConfigError::ValidationFailed { name, message, .. })
+                if name == "_a" && message.contains("'b,c'")),
+            "Expected ValidationFailed error, but got {:?}",
+            &config
+        );
+    }
+
     #[test]
     fn test_list_validator_any_non_duplicate_values() {
         let allow_any_non_duplicate_values = ValidList::any_non_duplicate_values(true);
@@ -477,6 +782,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_list_validator_items_validated_by() {
+        let validator = ValidList::items_validated_by(Range::between(0, 10));
+
+        validator.validate("test.config", "1, 5, 10").unwrap();
+        validator.validate("test.config", "").unwrap();
+
+        let res = validator.validate("test.config", "1, 11");
+        assert!(
+            matches!(&res, Err(ConfigError::ValidationFailed{..}) if res.as_ref().unwrap_err().to_string()
+                .eq("Validation failed for name 'test.config': Value 11 must be no more than 10")),
+            "Expected ValidationFailed error but got {:?}",
+            &res
+        );
+
+        let res = validator.validate("test.config", "1, a");
+        assert!(
+            matches!(&res, Err(ConfigError::InvalidValue { name, .. }) if name == "test.config"),
+            "Expected InvalidValue error but got {:?}",
+            &res
+        );
+
+        let res = validator.validate("test.config", "1, 1");
+        assert!(
+            matches!(&res, Err(ConfigError::ValidationFailed{..}) if res.as_ref().unwrap_err().to_string()
+                .eq("Validation failed for name 'test.config': \
+                Configuration 'test.config' values must not be duplicated.")),
+            "Expected ValidationFailed error but got {:?}",
+            &res
+        );
+
+        assert!(format!("{:?}", validator).starts_with("Validator([0, ..., 10] "));
+    }
+
     #[test]
     fn test_merge() {
         mod test_conf1 {
@@ -530,4 +869,87 @@ mod tests {
         assert_eq!(config.config1.b1(), "hello");
         assert_eq!(config.config2.b2(), "value2");
     }
+
+    #[test]
+    fn test_into_props_iter() {
+        mod test_conf {
+            use super::prelude::*;
+
+            #[derive(Debug, PartialEq, EasyConfig)]
+            pub struct NestedConfig {
+                #[attr(default = 5, importance = Importance::HIGH, documentation = "docs")]
+                pub a: i32,
+            }
+        }
+
+        #[derive(Debug, PartialEq, EasyConfig)]
+        struct TestConfig {
+            #[attr(name = "prop.b", importance = Importance::HIGH, documentation = "docs")]
+            b: Vec<String>,
+            #[attr(documentation = "docs")]
+            c: Option<bool>,
+            #[attr(documentation = "docs")]
+            d: Option<String>,
+            #[merge]
+            nested: test_conf::NestedConfig,
+        }
+
+        let mut props = HashMap::new();
+        props.insert("prop.b".to_string(), " x, y ".to_string());
+        props.insert("c".to_string(), "TRUE".to_string());
+        let config = TestConfig::from_props(&props).unwrap();
+
+        let pairs: Vec<(&'static str, String)> = TestConfig::into_props_iter(&config).collect();
+
+        // "d" is `None` and is skipped; the default of the merged "a" is emitted.
+        assert_eq!(
+            pairs,
+            vec![
+                ("prop.b", "x,y".to_string()),
+                ("c", "true".to_string()),
+                ("a", "5".to_string()),
+            ]
+        );
+
+        let round_trip =
+            TestConfig::from_props(&pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+                .unwrap();
+        assert_eq!(round_trip, config);
+    }
+
+    #[test]
+    fn test_config_schema() {
+        mod test_conf {
+            use super::prelude::*;
+
+            #[derive(EasyConfig)]
+            pub struct NestedConfig {
+                #[attr(default = 5, importance = Importance::HIGH, documentation = "docs")]
+                _a: i32,
+            }
+        }
+
+        #[derive(ConfigSchema)]
+        #[allow(dead_code)]
+        struct TestSchema {
+            #[attr(name = "prop.b", default = "hello".to_string(), importance = Importance::HIGH,
+            documentation = "docs", group = "group")]
+            b: String,
+            #[attr(validator = Range::between(0, 10))]
+            c: Option<i32>,
+            #[merge]
+            nested: test_conf::NestedConfig,
+        }
+
+        let def = TestSchema::config_def().unwrap();
+
+        assert_eq!(
+            def.config_keys().keys().copied().collect::<Vec<_>>(),
+            vec!["prop.b", "c", "_a"]
+        );
+        let b = def.find_key("prop.b").unwrap();
+        assert_eq!(b.default_value_str(), Some("hello".to_string()));
+        assert_eq!(b.group(), Some(&"group".to_string()));
+        assert!(def.find_key("c").unwrap().validator().is_some());
+    }
 }
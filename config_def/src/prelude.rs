@@ -4,8 +4,11 @@ pub use crate::core::{
     ConfigDef, ConfigKey, ConfigKeyTrait, ConfigValue, FromConfigDef, Importance,
 };
 pub use crate::errors::ConfigError;
+#[cfg(feature = "figment")]
+pub use crate::sources::figment_source::{ConfigDefProvider, FigmentSource};
+pub use crate::sources::{ConfigSource, properties_source::PropertiesSource};
 pub use crate::types::password::Password;
 pub use crate::validators::{
     Validator, range::Range, valid_list::ValidList, valid_string::ValidString,
 };
-pub use easy_config_macros::EasyConfig;
+pub use easy_config_macros::{ConfigSchema, EasyConfig};
@@ -0,0 +1,305 @@
+use crate::{ConfigDef, ConfigError, ConfigSource, FromConfigDef};
+use figment::providers::Serialized;
+use figment::value::{Dict, Map, Num, Value};
+use figment::{Figment, Metadata, Profile, Provider};
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+/// A `figment::Provider` that exposes the defaults declared in `T`'s `ConfigDef`.
+///
+/// Dotted key names (e.g. `server.port`) are emitted as nested dictionaries, so the
+/// defaults merge naturally with structured sources such as TOML or JSON files. Each
+/// default is emitted with the type given by its key's `schema_type()`: booleans and
+/// numbers as scalars, lists as arrays and maps as dictionaries, so the figment can be
+/// extracted straight into a `serde::Deserialize` struct. Keys without a default and
+/// password keys are not emitted.
+pub struct ConfigDefProvider<T: FromConfigDef> {
+    _config: PhantomData<fn() -> T>,
+}
+
+impl<T: FromConfigDef> ConfigDefProvider<T> {
+    pub fn new() -> Self {
+        Self {
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<T: FromConfigDef> Default for ConfigDefProvider<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FromConfigDef> Provider for ConfigDefProvider<T> {
+    fn metadata(&self) -> Metadata {
+        Metadata::named(std::any::type_name::<T>())
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
+        let def = T::config_def().map_err(|e| figment::Error::from(e.to_string()))?;
+        def.config_keys()
+            .values()
+            .filter(|key| key.schema_format() != Some("password"))
+            .filter_map(|key| {
+                key.default_value_str().map(|default| {
+                    Serialized::default(key.name(), default_value(key.schema_type(), &default))
+                })
+            })
+            .fold(Figment::new(), Figment::merge)
+            .data()
+    }
+}
+
+/// Converts a default rendered by `to_config_string` into the figment value of its schema type.
+fn default_value(schema_type: &str, default: &str) -> Value {
+    let number = match schema_type {
+        "integer" => default
+            .parse::<i64>()
+            .map(Num::from)
+            .or_else(|_| default.parse::<u64>().map(Num::from))
+            .ok(),
+        "number" => default.parse::<f64>().map(Num::from).ok(),
+        _ => None,
+    };
+    if let Some(number) = number {
+        return Value::from(number);
+    }
+    match schema_type {
+        "boolean" => default
+            .parse::<bool>()
+            .map_or_else(|_| Value::from(default), Value::from),
+        "array" if default.is_empty() => Value::from(Vec::<String>::new()),
+        "array" => Value::from(default.split(',').collect::<Vec<_>>()),
+        "object" => Value::from(
+            default
+                .split(',')
+                .filter_map(|entry| entry.split_once('='))
+                .map(|(k, v)| (k.to_string(), Value::from(v)))
+                .collect::<Dict>(),
+        ),
+        _ => Value::from(default),
+    }
+}
+
+/// A `ConfigSource` backed by a (possibly layered) `figment::Figment`.
+///
+/// The selected profile is extracted and flattened into string properties: nested
+/// dictionaries become dotted names and arrays are joined with `,`, matching how
+/// `Vec<String>` values are parsed. Dictionaries of map-valued keys registered with
+/// [`FigmentSource::with_config_def`] are rendered as `key=value,...` instead.
+pub struct FigmentSource {
+    figment: Figment,
+    map_names: HashSet<String>,
+}
+
+impl FigmentSource {
+    pub fn new(figment: Figment) -> Self {
+        Self {
+            figment,
+            map_names: HashSet::new(),
+        }
+    }
+
+    /// Registers the map-valued (`"object"`) keys of `def`, so their dictionaries are
+    /// loaded as single `key=value,...` properties rather than flattened into dotted names.
+    pub fn with_config_def(mut self, def: &ConfigDef) -> Self {
+        self.map_names.extend(
+            def.config_keys()
+                .values()
+                .filter(|key| key.schema_type() == "object")
+                .map(|key| key.name().to_string()),
+        );
+        self
+    }
+
+    fn flatten(
+        &self,
+        name: String,
+        value: Value,
+        props: &mut HashMap<String, String>,
+    ) -> Result<(), ConfigError> {
+        match value {
+            Value::Dict(_, dict) if self.map_names.contains(&name) => {
+                let entries = dict
+                    .into_iter()
+                    .map(|(key, value)| Ok(format!("{key}={}", scalar_to_string(&name, value)?)))
+                    .collect::<Result<Vec<_>, ConfigError>>()?;
+                props.insert(name, entries.join(","));
+            }
+            Value::Dict(_, dict) => {
+                for (key, value) in dict {
+                    self.flatten(format!("{name}.{key}"), value, props)?;
+                }
+            }
+            Value::Empty(..) => {}
+            Value::Array(_, values) => {
+                let items = values
+                    .into_iter()
+                    .map(|v| scalar_to_string(&name, v))
+                    .collect::<Result<Vec<_>, _>>()?;
+                props.insert(name, items.join(","));
+            }
+            value => {
+                let value = scalar_to_string(&name, value)?;
+                props.insert(name, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ConfigSource for FigmentSource {
+    fn load(&self) -> Result<HashMap<String, String>, ConfigError> {
+        let dict: Dict = self
+            .figment
+            .extract()
+            .map_err(|e| ConfigError::InvalidValue {
+                name: e.path.join("."),
+                message: e.kind.to_string(),
+                position: None,
+            })?;
+
+        let mut props = HashMap::new();
+        for (key, value) in dict {
+            self.flatten(key, value, &mut props)?;
+        }
+        Ok(props)
+    }
+}
+
+fn scalar_to_string(name: &str, value: Value) -> Result<String, ConfigError> {
+    match value {
+        Value::String(_, s) => Ok(s),
+        Value::Char(_, c) => Ok(c.to_string()),
+        Value::Bool(_, b) => Ok(b.to_string()),
+        Value::Num(_, n) => Ok(num_to_string(n)),
+        other => Err(ConfigError::InvalidValue {
+            name: name.to_string(),
+            message: format!("Unsupported value type: {}", other.to_actual()),
+            position: None,
+        }),
+    }
+}
+
+fn num_to_string(n: Num) -> String {
+    match n {
+        Num::F32(f) => f.to_string(),
+        Num::F64(f) => f.to_string(),
+        n => n
+            .to_u128()
+            .map(|u| u.to_string())
+            .or_else(|| n.to_i128().map(|i| i.to_string()))
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, EasyConfig)]
+    struct TestConfig {
+        #[attr(name = "server.port", default = 8080, validator = Range::between(1, 65535))]
+        port: u16,
+        #[attr(name = "server.host", default = "localhost".to_string())]
+        host: String,
+        #[attr(default = true)]
+        flag: bool,
+        #[attr(default = vec!["a".to_string(), "b".to_string()])]
+        tags: Vec<String>,
+        #[attr(default = BTreeMap::from([("k".to_string(), "v".to_string())]))]
+        labels: BTreeMap<String, String>,
+        #[attr(default = Password::new("secret".to_string()))]
+        password: Password,
+        #[attr(documentation = "docs")]
+        ratio: Option<f64>,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Server {
+        port: u16,
+        host: String,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Settings {
+        server: Server,
+        flag: bool,
+        tags: Vec<String>,
+        labels: BTreeMap<String, String>,
+        password: Option<String>,
+        ratio: Option<f64>,
+    }
+
+    #[test]
+    fn test_config_def_provider_extracts_typed_defaults() {
+        let figment = Figment::from(ConfigDefProvider::<TestConfig>::new());
+
+        assert_eq!(
+            figment.extract::<Settings>().unwrap(),
+            Settings {
+                server: Server {
+                    port: 8080,
+                    host: "localhost".to_string(),
+                },
+                flag: true,
+                tags: vec!["a".to_string(), "b".to_string()],
+                labels: BTreeMap::from([("k".to_string(), "v".to_string())]),
+                password: None,
+                ratio: None,
+            }
+        );
+        assert!(figment.find_value("password").is_err());
+        assert!(figment.find_value("ratio").is_err());
+    }
+
+    #[test]
+    fn test_figment_source_layers_into_from_props() {
+        let figment = Figment::from(ConfigDefProvider::<TestConfig>::new())
+            .merge(Serialized::default("server.port", 9090))
+            .merge(Serialized::default("tags", vec!["x", "y"]))
+            .merge(Serialized::default("labels.k2", "v2"))
+            .merge(Serialized::default("ratio", 0.5));
+
+        let props = FigmentSource::new(figment)
+            .with_config_def(&TestConfig::config_def().unwrap())
+            .load()
+            .unwrap();
+        assert_eq!(props.get("server.port").unwrap(), "9090");
+        assert_eq!(props.get("tags").unwrap(), "x,y");
+        assert_eq!(props.get("labels").unwrap(), "k=v,k2=v2");
+
+        let config = TestConfig::from_props(&props).unwrap();
+        assert_eq!(
+            config,
+            TestConfig {
+                port: 9090,
+                host: "localhost".to_string(),
+                flag: true,
+                tags: vec!["x".to_string(), "y".to_string()],
+                labels: BTreeMap::from([
+                    ("k".to_string(), "v".to_string()),
+                    ("k2".to_string(), "v2".to_string()),
+                ]),
+                password: Password::new("secret".to_string()),
+                ratio: Some(0.5),
+            }
+        );
+    }
+
+    #[test]
+    fn test_figment_source_rejects_nested_arrays() {
+        let figment = Figment::new().merge(Serialized::default("a", vec![vec![1]]));
+
+        let res = FigmentSource::new(figment).load();
+
+        assert!(
+            matches!(&res, Err(ConfigError::InvalidValue { name, .. }) if name == "a"),
+            "Expected InvalidValue error but got {:?}",
+            &res
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use crate::ConfigError;
+use std::collections::HashMap;
+
+#[cfg(feature = "figment")]
+pub(crate) mod figment_source;
+pub(crate) mod properties_source;
+
+/// A provider of raw string properties that can be fed into `FromConfigDef::from_props`.
+///
+/// Implementations hide where the configuration comes from (files, environment, other
+/// configuration libraries) and only expose the flat `name -> value` map the
+/// `EasyConfig` parsing works with.
+pub trait ConfigSource {
+    /// Loads all properties known to this source.
+    fn load(&self) -> Result<HashMap<String, String>, ConfigError>;
+}
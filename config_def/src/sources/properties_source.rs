@@ -0,0 +1,190 @@
+use crate::{ConfigError, ConfigSource, FromConfigDef};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A `ConfigSource` reading Java-style `.properties` content.
+///
+/// Each non-blank line that doesn't start with `#` or `!` is a `name=value`, `name: value` or
+/// `name value` pair: as in `java.util.Properties`, the name ends at the first `=`, `:` or
+/// whitespace, and whitespace around the separator is ignored. Values are trimmed and later
+/// lines override earlier ones. Line continuations and escapes are not supported.
+///
+/// The source remembers where each value was defined, so errors produced while parsing its
+/// properties can be annotated with a line number via [`PropertiesSource::locate`].
+pub struct PropertiesSource {
+    props: HashMap<String, String>,
+    positions: HashMap<String, (usize, usize)>,
+}
+
+impl PropertiesSource {
+    /// Parses `.properties` content.
+    pub fn new(content: &str) -> Self {
+        let mut props = HashMap::new();
+        let mut positions = HashMap::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
+                continue;
+            }
+            let name_start = line.len() - trimmed.len();
+            let name_end = trimmed
+                .find(|c: char| c == '=' || c == ':' || c.is_whitespace())
+                .map_or(line.len(), |end| name_start + end);
+            let separator = line[name_end..].trim_start();
+            let value_start =
+                line.len() - separator.len() + usize::from(separator.starts_with(['=', ':']));
+            let value = &line[value_start..];
+            let value_offset = value_start + (value.len() - value.trim_start().len());
+            let column = line[..value_offset].chars().count() + 1;
+
+            let name = line[name_start..name_end].to_string();
+            positions.insert(name.clone(), (index + 1, column));
+            props.insert(name, value.trim().to_string());
+        }
+
+        Self { props, positions }
+    }
+
+    /// Reads and parses a `.properties` file.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::new(&std::fs::read_to_string(path)?))
+    }
+
+    /// Returns the 1-based `(line, column)` at which the value of `name` starts.
+    pub fn position(&self, name: &str) -> Option<(usize, usize)> {
+        self.positions.get(name).copied()
+    }
+
+    /// Attaches the position of the offending property to `error`, if it came from this source.
+    ///
+    /// Only pass errors raised while parsing or validating values from this source: schema
+    /// errors from `config_def()` (e.g. a key defined twice) would otherwise point at an
+    /// unrelated line of the file.
+    pub fn locate(&self, error: ConfigError) -> ConfigError {
+        let position = match &error {
+            ConfigError::InvalidValue { name, .. } | ConfigError::ValidationFailed { name, .. } => {
+                self.position(name)
+            }
+            ConfigError::MissingName(_) => None,
+        };
+        match position {
+            Some((line, column)) => error.with_position(line, column),
+            None => error,
+        }
+    }
+
+    /// Parses the properties into `T`, annotating value errors with their source position.
+    ///
+    /// Errors in `T`'s schema itself are returned without a position.
+    pub fn load_into<T: FromConfigDef>(&self) -> Result<T, ConfigError> {
+        T::config_def()?;
+        T::from_props(&self.props).map_err(|e| self.locate(e))
+    }
+}
+
+impl ConfigSource for PropertiesSource {
+    fn load(&self) -> Result<HashMap<String, String>, ConfigError> {
+        Ok(self.props.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    const CONTENT: &str = "# comment\n\
+        ! another comment\n\
+        \n\
+        server.port = 8080\n\
+        server.host:localhost\n\
+        flag\n\
+        server.port=9090\n";
+
+    #[test]
+    fn test_parse_properties() {
+        let source = PropertiesSource::new(CONTENT);
+
+        let props = source.load().unwrap();
+
+        assert_eq!(
+            props,
+            HashMap::from([
+                ("server.port".to_string(), "9090".to_string()),
+                ("server.host".to_string(), "localhost".to_string()),
+                ("flag".to_string(), "".to_string()),
+            ])
+        );
+        assert_eq!(source.position("server.port"), Some((7, 13)));
+        assert_eq!(source.position("server.host"), Some((5, 13)));
+        assert_eq!(source.position("flag"), Some((6, 5)));
+        assert_eq!(source.position("missing"), None);
+    }
+
+    #[test]
+    fn test_parse_whitespace_separated_properties() {
+        let source = PropertiesSource::new(
+            "server.port 8080\n  server.host\t = localhost\nurl http://a:1\n",
+        );
+
+        assert_eq!(
+            source.load().unwrap(),
+            HashMap::from([
+                ("server.port".to_string(), "8080".to_string()),
+                ("server.host".to_string(), "localhost".to_string()),
+                ("url".to_string(), "http://a:1".to_string()),
+            ])
+        );
+        assert_eq!(source.position("server.port"), Some((1, 13)));
+        assert_eq!(source.position("server.host"), Some((2, 18)));
+        assert_eq!(source.position("url"), Some((3, 5)));
+    }
+
+    #[test]
+    fn test_errors_include_line_number() {
+        #[derive(Debug, EasyConfig)]
+        struct TestConfig {
+            #[attr(name = "a", validator = Range::between(0, 10))]
+            _a: i32,
+            #[attr(name = "b")]
+            _b: i32,
+        }
+
+        let source = PropertiesSource::new("a = 5\nb = x\n");
+        let res = source.load_into::<TestConfig>();
+        assert!(
+            matches!(&res, Err(ConfigError::InvalidValue { name, position: Some((2, 5)), .. })
+                if name == "b"),
+            "Expected InvalidValue error but got {:?}",
+            &res
+        );
+
+        let source = PropertiesSource::new("b = 1\n\na = 11\n");
+        let res = source.load_into::<TestConfig>();
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Validation failed for name 'a': Value 11 must be no more than 10 at line 3"
+        );
+    }
+
+    #[test]
+    fn test_schema_errors_have_no_position() {
+        #[derive(Debug, EasyConfig)]
+        struct DuplicateKeyConfig {
+            #[attr(name = "a", default = 1)]
+            _a1: i32,
+            #[attr(name = "a", default = 2)]
+            _a2: i32,
+        }
+
+        let source = PropertiesSource::new("\n\na = 5\n");
+        let res = source.load_into::<DuplicateKeyConfig>();
+        assert!(
+            matches!(&res, Err(ConfigError::ValidationFailed { name, position: None, .. })
+                if name == "a"),
+            "Expected ValidationFailed error without position but got {:?}",
+            &res
+        );
+    }
+}
@@ -12,6 +12,15 @@ pub trait Validator: Display + Send + Sync {
     /// The core validation method. It operates on the raw string value.
     fn validate(&self, name: &str, value: &str) -> Result<(), ConfigError>;
 
+    /// Returns whether `value` passes validation, discarding the error.
+    ///
+    /// The value is validated under the sentinel name `"__check__"`, so any error message
+    /// the validator would produce is meaningless here.
+    #[inline]
+    fn is_value_valid(&self, value: &str) -> bool {
+        self.validate("__check__", value).is_ok()
+    }
+
     fn box_clone(&self) -> Box<dyn Validator>;
 }
 
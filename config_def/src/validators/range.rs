@@ -33,6 +33,7 @@ impl Validator for Range {
             .map_err(|_| ConfigError::InvalidValue {
                 name: name.to_string(),
                 message: "Value is not a valid number".to_string(),
+                position: None,
             })?;
 
         if let Some(min) = self.min
@@ -41,6 +42,7 @@ impl Validator for Range {
             return Err(ConfigError::ValidationFailed {
                 name: name.to_string(),
                 message: format!("Value {} must be at least {}", n, min),
+                position: None,
             });
         }
 
@@ -50,6 +52,7 @@ impl Validator for Range {
             return Err(ConfigError::ValidationFailed {
                 name: name.to_string(),
                 message: format!("Value {} must be no more than {}", n, max),
+                position: None,
             });
         }
 
@@ -5,12 +5,14 @@ use std::fmt::Display;
 
 /// A stateful validator for comma-separated lists.
 ///
-/// It can check for duplicate values, enforce a specific set of allowed values,
-/// and control whether an empty list is permitted.
+/// It can check for duplicate values, enforce a specific set of allowed values
+/// (or run an arbitrary validator on every item), and control whether an empty list
+/// is permitted.
 #[derive(Clone, Debug)]
 pub struct ValidList {
     valid_string: ValidString,
     is_empty_allowed: bool,
+    item_validator: Option<Box<dyn Validator>>,
 }
 
 impl ValidList {
@@ -19,6 +21,7 @@ impl ValidList {
         Self {
             valid_string: ValidString::new(valid_strings),
             is_empty_allowed,
+            item_validator: None,
         }
     }
 
@@ -52,6 +55,17 @@ impl ValidList {
             is_empty_allowed,
         ))
     }
+
+    /// Creates a validator that runs `item_validator` against every value of the list.
+    /// Allows empty lists by default; like every `ValidList`, duplicate values are rejected.
+    ///
+    /// Example: `ValidList::items_validated_by(Range::between(0, 10))`
+    pub fn items_validated_by(item_validator: Box<dyn Validator>) -> Box<dyn Validator> {
+        Box::new(Self {
+            item_validator: Some(item_validator),
+            ..Self::new(Vec::new(), true)
+        })
+    }
 }
 
 impl Validator for ValidList {
@@ -81,6 +95,7 @@ impl Validator for ValidList {
                     "Configuration '{}' must not be empty. Valid values include: {}",
                     name, valid_values_str
                 ),
+                position: None,
             });
         }
 
@@ -90,6 +105,7 @@ impl Validator for ValidList {
             return Err(ConfigError::ValidationFailed {
                 name: name.to_string(),
                 message: format!("Configuration '{}' values must not be duplicated.", name),
+                position: None,
             });
         }
 
@@ -99,6 +115,7 @@ impl Validator for ValidList {
                 return Err(ConfigError::ValidationFailed {
                     name: name.to_string(),
                     message: format!("Configuration '{}' values must not be empty.", name),
+                    position: None,
                 });
             }
             if !self.valid_string.valid_strings().is_empty()
@@ -112,8 +129,12 @@ impl Validator for ValidList {
                         name,
                         self.valid_string.valid_strings().join(", ")
                     ),
+                    position: None,
                 });
             }
+            if let Some(item_validator) = &self.item_validator {
+                item_validator.validate(name, val)?;
+            }
         }
 
         Ok(())
@@ -129,7 +150,10 @@ impl Display for ValidList {
         write!(
             f,
             "{} (empty config {})",
-            self.valid_string,
+            match &self.item_validator {
+                Some(item_validator) => item_validator.to_string(),
+                None => self.valid_string.to_string(),
+            },
             if self.is_empty_allowed {
                 "empty config allowed"
             } else {
@@ -23,6 +23,33 @@ impl ValidString {
         ))
     }
 
+    /// Factory for a validator that rejects empty and whitespace-only strings.
+    ///
+    /// Validation runs on the raw value, before `String::parse` trims it, so `"   "` is
+    /// rejected instead of silently becoming an empty string.
+    pub fn non_empty() -> Box<dyn Validator> {
+        Box::new(NonEmptyString)
+    }
+
+    /// Factory for a validator accepting a single DNS label, e.g. `my-host`.
+    ///
+    /// A label is 1-63 ASCII alphanumeric characters or hyphens, doesn't start with a digit
+    /// and doesn't start or end with a hyphen. Leading digits are rejected (unlike RFC 1123)
+    /// so that numeric values such as `123` are not accepted as host names.
+    pub fn dns_label() -> Box<dyn Validator> {
+        Box::new(DnsName { multi_label: false })
+    }
+
+    /// Factory for a validator accepting a full domain name made of dot-separated DNS labels,
+    /// e.g. `broker-1.example.com`. The whole name must not exceed 253 characters.
+    ///
+    /// Labels follow the `dns_label` rules except that they may start with a digit
+    /// (e.g. `163.com`); only an all-numeric final label is rejected, so that IP address
+    /// literals such as `1.2.3.4` are not accepted.
+    pub fn dns_name() -> Box<dyn Validator> {
+        Box::new(DnsName { multi_label: true })
+    }
+
     pub(crate) fn valid_strings(&self) -> &Vec<String> {
         self.valid_strings.as_ref()
     }
@@ -35,6 +62,7 @@ impl Validator for ValidString {
             Err(ConfigError::ValidationFailed {
                 name: name.to_string(),
                 message: format!("String must be one of: {}", self.valid_strings.join(", ")),
+                position: None,
             })
         } else {
             Ok(())
@@ -51,3 +79,115 @@ impl Display for ValidString {
         write!(f, "[{}]", self.valid_strings.join(", "))
     }
 }
+
+/// A validator for non-blank strings, created via `ValidString::non_empty`.
+#[derive(Clone, Debug)]
+struct NonEmptyString;
+
+impl Validator for NonEmptyString {
+    fn validate(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        if value.trim().is_empty() {
+            Err(ConfigError::ValidationFailed {
+                name: name.to_string(),
+                message: "String must not be empty or whitespace-only".to_string(),
+                position: None,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Validator> {
+        Box::new(self.clone())
+    }
+}
+
+impl Display for NonEmptyString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[non-empty string]")
+    }
+}
+
+const MAX_DNS_LABEL_LEN: usize = 63;
+const MAX_DNS_NAME_LEN: usize = 253;
+
+/// A validator for DNS labels and domain names, created via `ValidString::dns_label`
+/// and `ValidString::dns_name`.
+#[derive(Clone, Debug)]
+struct DnsName {
+    multi_label: bool,
+}
+
+impl DnsName {
+    fn check_label(label: &str, allow_leading_digit: bool) -> Result<(), String> {
+        if label.is_empty() || label.len() > MAX_DNS_LABEL_LEN {
+            return Err(format!(
+                "Label '{}' must be between 1 and {} characters long",
+                label, MAX_DNS_LABEL_LEN
+            ));
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!(
+                "Label '{}' must contain only alphanumeric characters and hyphens",
+                label
+            ));
+        }
+        if !allow_leading_digit && label.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(format!("Label '{}' must not start with a digit", label));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(format!(
+                "Label '{}' must not start or end with a hyphen",
+                label
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validator for DnsName {
+    fn validate(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        let s = value.trim();
+        let result = if self.multi_label {
+            if s.len() > MAX_DNS_NAME_LEN {
+                Err(format!(
+                    "Domain name must be at most {} characters long",
+                    MAX_DNS_NAME_LEN
+                ))
+            } else if s
+                .rsplit('.')
+                .next()
+                .is_some_and(|top| !top.is_empty() && top.chars().all(|c| c.is_ascii_digit()))
+            {
+                Err(format!(
+                    "Domain name '{}' must not end with an all-numeric label",
+                    s
+                ))
+            } else {
+                s.split('.')
+                    .try_for_each(|label| Self::check_label(label, true))
+            }
+        } else {
+            Self::check_label(s, false)
+        };
+        result.map_err(|message| ConfigError::ValidationFailed {
+            name: name.to_string(),
+            message,
+            position: None,
+        })
+    }
+
+    fn box_clone(&self) -> Box<dyn Validator> {
+        Box::new(self.clone())
+    }
+}
+
+impl Display for DnsName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.multi_label {
+            write!(f, "[DNS name]")
+        } else {
+            write!(f, "[DNS label]")
+        }
+    }
+}
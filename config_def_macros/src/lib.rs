@@ -1,7 +1,8 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{
-    Data, DeriveInput, Expr, Fields, GenericArgument, Lit, Meta, PathArguments, Type,
+    Data, DeriveInput, Expr, Field, Fields, GenericArgument, Lit, Meta, PathArguments, Type,
     parse_macro_input, punctuated::Punctuated, token,
 };
 
@@ -9,17 +10,11 @@ use syn::{
 pub fn easy_config_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let struct_name = &input.ident;
-
-    let fields = match input.data {
-        Data::Struct(data) => match data.fields {
-            Fields::Named(fields) => fields.named,
-            _ => panic!("Only structs with named fields are supported"),
-        },
-        _ => panic!("Only structs are supported"),
-    };
+    let fields = named_fields(input.data);
 
     let mut config_key_inits = Vec::new();
     let mut from_props_fields = Vec::new();
+    let mut props_iter_fields = Vec::new();
     let mut getter_methods = Vec::new();
 
     for f in fields.iter() {
@@ -33,16 +28,11 @@ pub fn easy_config_derive(input: TokenStream) -> TokenStream {
             from_props_fields.push(quote! {
                 #field_name: <#field_ty as FromConfigDef>::from_props(props)?
             });
+            props_iter_fields.push(quote! {
+                props.extend(<#field_ty as FromConfigDef>::into_props_iter(&instance.#field_name));
+            });
         } else {
-            let mut attrs = ParsedAttributes::default();
-            for attr in &f.attrs {
-                if attr.path().is_ident("attr") {
-                    let parsed = attr
-                        .parse_args_with(Punctuated::<Meta, token::Comma>::parse_terminated)
-                        .expect("Failed to parse config attributes");
-                    attrs.populate_from(parsed);
-                }
-            }
+            let attrs = ParsedAttributes::from_field(f);
 
             if attrs.getter {
                 getter_methods.push(quote! {
@@ -50,66 +40,13 @@ pub fn easy_config_derive(input: TokenStream) -> TokenStream {
                 });
             }
 
-            let field_name_str = field_name.to_string();
-            let lookup_key = attrs.name.map_or(quote! { #field_name_str }, |e| {
-                if let Expr::Lit(expr_lit) = &e
-                    && let Lit::Str(_) = &expr_lit.lit
-                {
-                    return quote! { #e };
-                }
-                quote! { &*(#e) }
-            });
-            let docs = attrs
-                .documentation
-                .map(|d| quote! { Some(Into::<String>::into(#d)) })
-                .unwrap_or(quote! { None });
-            let default = attrs
-                .default
-                .map(|d| quote! { Some(#d) })
-                .unwrap_or(quote! { None });
-            let importance = attrs
-                .importance
-                .map(|i| quote! { Some(#i) })
-                .unwrap_or(quote! { None });
-            let validator = attrs
-                .validator
-                .map(|v| quote! { Some(#v) })
-                .unwrap_or(quote! { None });
-            let group = attrs
-                .group
-                .map(|g| quote! { Some(Into::<String>::into(#g)) })
-                .unwrap_or(quote! { None });
-            let internal_config = attrs.internal_config;
-
-            let (is_option, inner_ty) = {
-                let mut is_opt = false;
-                let mut inner = quote! { #field_ty };
-
-                if let Type::Path(type_path) = field_ty
-                    && type_path.path.segments.len() == 1
-                    && type_path.path.segments[0].ident == "Option"
-                    && let PathArguments::AngleBracketed(params) =
-                        &type_path.path.segments[0].arguments
-                    && let Some(GenericArgument::Type(t)) = params.args.first()
-                {
-                    is_opt = true;
-                    inner = quote! { #t };
-                }
-
-                (is_opt, inner)
-            };
-
-            config_key_inits.push(quote! {
-                vec![Box::new(ConfigKey::<#inner_ty>::new(
-                    #lookup_key,
-                    #docs,
-                    #default,
-                    #validator,
-                    #importance,
-                    #group,
-                    #internal_config,
-                )) as Box<dyn ConfigKeyTrait>]
-            });
+            let FieldKey {
+                lookup_key,
+                inner_ty,
+                is_option,
+                init,
+            } = FieldKey::new(f, attrs);
+            config_key_inits.push(init);
 
             // Reverted to separate logic paths for `T` and `Option<T>` to fix the error.
             let from_props_logic = if is_option {
@@ -162,12 +99,23 @@ pub fn easy_config_derive(input: TokenStream) -> TokenStream {
                 }
             };
             from_props_fields.push(from_props_logic);
+
+            let props_iter_logic = if is_option {
+                quote! {
+                    if let Some(val) = &instance.#field_name {
+                        props.push((#lookup_key, <#inner_ty as ConfigValue>::to_config_string(val)));
+                    }
+                }
+            } else {
+                quote! {
+                    props.push((#lookup_key, <#inner_ty as ConfigValue>::to_config_string(&instance.#field_name)));
+                }
+            };
+            props_iter_fields.push(props_iter_logic);
         }
     }
 
     let expanded = quote! {
-        static CONFIG_DEF: once_cell::sync::OnceCell<ConfigDef> = once_cell::sync::OnceCell::new();
-
         impl #struct_name {
             #(#getter_methods)*
         }
@@ -179,6 +127,52 @@ pub fn easy_config_derive(input: TokenStream) -> TokenStream {
             }
 
             fn config_def() -> Result<&'static ConfigDef, ConfigError> {
+                static CONFIG_DEF: once_cell::sync::OnceCell<ConfigDef> = once_cell::sync::OnceCell::new();
+                CONFIG_DEF.get_or_try_init(|| {
+                    let keys: Vec<Box<dyn ConfigKeyTrait>> = vec![
+                        #(#config_key_inits),*
+                    ].into_iter().flatten().collect();
+                    ConfigDef::try_from(keys)
+                })
+            }
+
+            fn into_props_iter(instance: &Self) -> impl Iterator<Item = (&'static str, String)> {
+                let mut props: Vec<(&'static str, String)> = Vec::new();
+                #(#props_iter_fields)*
+                props.into_iter()
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+/// Generates only the static `config_def()` schema for a struct, without `from_props`.
+///
+/// Fields are annotated with the same `#[attr(...)]` and `#[merge]` attributes as for
+/// `EasyConfig`. The generated `config_def()` is an inherent method, so it doesn't clash with
+/// `FromConfigDef::config_def()`, and merged fields may derive either macro.
+#[proc_macro_derive(ConfigSchema, attributes(attr, merge))]
+pub fn config_schema_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+    let fields = named_fields(input.data);
+
+    let config_key_inits = fields.iter().map(|f| {
+        let field_ty = &f.ty;
+        if f.attrs.iter().any(|attr| attr.path().is_ident("merge")) {
+            quote! {
+                <#field_ty>::config_def()?.config_keys().values().cloned().collect::<Vec<_>>()
+            }
+        } else {
+            FieldKey::new(f, ParsedAttributes::from_field(f)).init
+        }
+    });
+
+    let expanded = quote! {
+        impl #struct_name {
+            /// Provides access to the static configuration schema (`ConfigDef`).
+            pub fn config_def() -> Result<&'static ConfigDef, ConfigError> {
+                static CONFIG_DEF: once_cell::sync::OnceCell<ConfigDef> = once_cell::sync::OnceCell::new();
                 CONFIG_DEF.get_or_try_init(|| {
                     let keys: Vec<Box<dyn ConfigKeyTrait>> = vec![
                         #(#config_key_inits),*
@@ -191,6 +185,109 @@ pub fn easy_config_derive(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+fn named_fields(data: Data) -> Punctuated<Field, token::Comma> {
+    match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => panic!("Only structs with named fields are supported"),
+        },
+        _ => panic!("Only structs are supported"),
+    }
+}
+
+/// The `ConfigKey` metadata generated for a single (non-merged) field.
+struct FieldKey {
+    /// The expression evaluating to the key name as `&'static str`.
+    lookup_key: TokenStream2,
+    /// The field type, with `Option` stripped.
+    inner_ty: TokenStream2,
+    is_option: bool,
+    /// The expression building a `Vec<Box<dyn ConfigKeyTrait>>` holding the key.
+    init: TokenStream2,
+}
+
+impl FieldKey {
+    fn new(f: &Field, attrs: ParsedAttributes) -> Self {
+        let field_name = f.ident.as_ref().unwrap();
+        let field_ty = &f.ty;
+
+        let field_name_str = field_name.to_string();
+        let lookup_key = attrs.name.map_or(quote! { #field_name_str }, |e| {
+            if let Expr::Lit(expr_lit) = &e
+                && let Lit::Str(_) = &expr_lit.lit
+            {
+                return quote! { #e };
+            }
+            quote! { &*(#e) }
+        });
+        let docs = attrs
+            .documentation
+            .map(|d| quote! { Some(Into::<String>::into(#d)) })
+            .unwrap_or(quote! { None });
+        let has_default = attrs.default.is_some();
+        let default = attrs
+            .default
+            .map(|d| quote! { Some(#d) })
+            .unwrap_or(quote! { None });
+        let importance = attrs
+            .importance
+            .map(|i| quote! { Some(#i) })
+            .unwrap_or(quote! { None });
+        let validator = attrs
+            .validator
+            .map(|v| quote! { Some(#v) })
+            .unwrap_or(quote! { None });
+        let group = attrs
+            .group
+            .map(|g| quote! { Some(Into::<String>::into(#g)) })
+            .unwrap_or(quote! { None });
+        let internal_config = attrs.internal_config;
+        let since_version = attrs
+            .since_version
+            .map(|v| quote! { Some(#v) })
+            .unwrap_or(quote! { None });
+
+        let (is_option, inner_ty) = {
+            let mut is_opt = false;
+            let mut inner = quote! { #field_ty };
+
+            if let Type::Path(type_path) = field_ty
+                && type_path.path.segments.len() == 1
+                && type_path.path.segments[0].ident == "Option"
+                && let PathArguments::AngleBracketed(params) = &type_path.path.segments[0].arguments
+                && let Some(GenericArgument::Type(t)) = params.args.first()
+            {
+                is_opt = true;
+                inner = quote! { #t };
+            }
+
+            (is_opt, inner)
+        };
+        let required = !is_option && !has_default;
+
+        let init = quote! {
+            vec![Box::new(ConfigKey::<#inner_ty>::new(
+                #lookup_key,
+                #docs,
+                #default,
+                #validator,
+                #importance,
+                #group,
+                #internal_config,
+            )
+            .with_since_version(#since_version)
+            .with_required(#required)) as Box<dyn ConfigKeyTrait>]
+        };
+
+        Self {
+            lookup_key,
+            inner_ty,
+            is_option,
+            init,
+        }
+    }
+}
+
 /// A helper struct to organize parsed attributes within the macro.
 #[derive(Default)]
 struct ParsedAttributes {
@@ -200,11 +297,25 @@ struct ParsedAttributes {
     group: Option<Expr>,
     importance: Option<Expr>,
     validator: Option<Expr>,
+    since_version: Option<Expr>,
     getter: bool,
     internal_config: bool,
 }
 
 impl ParsedAttributes {
+    fn from_field(f: &Field) -> Self {
+        let mut attrs = ParsedAttributes::default();
+        for attr in &f.attrs {
+            if attr.path().is_ident("attr") {
+                let parsed = attr
+                    .parse_args_with(Punctuated::<Meta, token::Comma>::parse_terminated)
+                    .expect("Failed to parse config attributes");
+                attrs.populate_from(parsed);
+            }
+        }
+        attrs
+    }
+
     fn populate_from(&mut self, parsed_attrs: Punctuated<Meta, token::Comma>) {
         for meta in parsed_attrs {
             match meta {
@@ -220,6 +331,7 @@ impl ParsedAttributes {
                         "group" => self.group = Some(nv.value),
                         "importance" => self.importance = Some(nv.value),
                         "validator" => self.validator = Some(nv.value),
+                        "since_version" => self.since_version = Some(nv.value),
                         "internal_config" => {
                             if let Expr::Lit(expr_lit) = nv.value
                                 && let Lit::Bool(lit_bool) = expr_lit.lit